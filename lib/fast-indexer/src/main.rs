@@ -33,12 +33,40 @@
 //! - `soft_blocks`: Computed inverse of soft_depends_on (informational, non-blocking)
 //! - `children`: Computed inverse of parent
 //!
+//! ### Project Metadata Inheritance
+//!
+//! A task's frontmatter may set `inherit: <path>` (relative to the task's own
+//! directory) to pull unset fields from a shared defaults file, e.g. a
+//! `_project.md` with `project`/`assignee`/`tags` set once for a whole
+//! directory. Inherited values only fill fields the task left empty, `tags`
+//! and other arrays union rather than overwrite, and a task can list
+//! `unset: [assignee]` to opt out of a specific inherited key. Resolution
+//! happens in `parse_file`, so every downstream consumer (`Node`,
+//! `McpIndexEntry`) sees the fully-resolved fields and never needs to chase
+//! the include itself.
+//!
 //! ## Output Formats
 //!
 //! - **JSON**: Standard node-link format with all metadata fields
 //! - **GraphML**: XML format compatible with yEd, Gephi, Cytoscape (includes all metadata as node attributes)
 //! - **DOT**: Graphviz format (text-based, suitable for Graphviz layout engines)
 //! - **MCP Index**: JSON task index matching task_index.py schema
+//! - **Search Index**: Inverted-index JSON for fuzzy full-text search over title and body
+//! - **HTML**: Self-contained interactive viewer (embedded graph + search index, live filters)
+//!
+//! ## Watch Mode
+//!
+//! `--watch` keeps the process running after the initial scan and regenerates
+//! every output whenever a markdown file under `root` changes, debouncing
+//! bursts of events (see `--debounce-ms`) so editors' save patterns only
+//! trigger one regeneration.
+//!
+//! ## Fuzzy Task Lookup
+//!
+//! The MCP index carries a precomputed CharBag (see `CharBag`) per task over
+//! its `id` + `title`, so a picker can match by partial/misspelled input
+//! without re-scanning. `--query <str>` runs this lookup directly and prints
+//! ranked matches instead of writing output files.
 
 use anyhow::Result;
 use chrono::Utc;
@@ -47,6 +75,7 @@ use gray_matter::engine::YAML;
 use gray_matter::Matter;
 use ignore::WalkBuilder;
 use md5;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -54,6 +83,9 @@ use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+use toml::Value as TomlValue;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -66,10 +98,23 @@ struct Args {
     #[arg(short, long, default_value = "graph")]
     output: String,
 
-    /// Output format: json, graphml, dot, mcp-index, all (default: all)
+    /// Output format: json, graphml, dot, html, mcp-index, search-index, all (default: all)
     #[arg(short, long, default_value = "all")]
     format: String,
 
+    /// Full-text search query; runs against the search index instead of building output files
+    #[arg(long)]
+    search: Option<String>,
+
+    /// Max number of results to return for --search / --query
+    #[arg(long, default_value = "10")]
+    top_n: usize,
+
+    /// Fuzzy task lookup by partial/misspelled title or id; runs against the
+    /// MCP index's CharBag-accelerated matcher instead of building output files
+    #[arg(long)]
+    query: Option<String>,
+
     /// Filter by frontmatter type (e.g., task,project,goal)
     #[arg(short = 't', long, value_delimiter = ',')]
     filter_type: Option<Vec<String>>,
@@ -86,11 +131,290 @@ struct Args {
     #[arg(long)]
     tasks_dir: Option<String>,
 
+    /// Path to the config file (default: <root>/.fastindexer.toml if present)
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Bypass the incremental parse cache and force a full reparse of every file
+    #[arg(long, visible_alias = "force")]
+    no_cache: bool,
+
+    /// After the initial scan, keep running and regenerate the outputs whenever
+    /// markdown files under `root` change (create/modify/delete/rename)
+    #[arg(long)]
+    watch: bool,
+
+    /// How long to wait for a burst of filesystem events to settle before
+    /// regenerating, in --watch mode
+    #[arg(long, default_value = "300")]
+    debounce_ms: u64,
+
     /// Suppress informational output
     #[arg(short, long)]
     quiet: bool,
 }
 
+/// Default config filename, looked for at the scan root.
+const CONFIG_FILENAME: &str = ".fastindexer.toml";
+
+/// Default filter values loaded from a config's `[filters]` table; used when
+/// the corresponding CLI flag isn't set.
+#[derive(Debug, Default, Clone)]
+struct FilterDefaults {
+    filter_type: Option<Vec<String>>,
+    status: Option<Vec<String>>,
+    priority: Option<Vec<i32>>,
+}
+
+/// Layered, cascading config for `.fastindexer.toml`. Lets vaults declare their
+/// own status vocabulary and remap frontmatter keys onto `Node` fields instead
+/// of being locked into the built-in defaults.
+#[derive(Debug, Default, Clone)]
+struct FastIndexerConfig {
+    /// user status -> canonical status (e.g. "backlog" -> "active")
+    status_aliases: HashMap<String, String>,
+    /// frontmatter key -> canonical Node/FileData field name
+    field_map: HashMap<String, String>,
+    filters: FilterDefaults,
+    /// DOT output color/style overrides, keyed by e.g. "edge.depends_on" or
+    /// "node.status.blocked"; see `edge_style`/`node_status_style` for the
+    /// recognized keys and their built-in defaults.
+    dot_palette: HashMap<String, String>,
+}
+
+impl FastIndexerConfig {
+    /// Merge another layer's keys on top of this one (child overrides parent).
+    fn merge_from(&mut self, other: FastIndexerConfig) {
+        for (k, v) in other.status_aliases {
+            self.status_aliases.insert(k, v);
+        }
+        for (k, v) in other.field_map {
+            self.field_map.insert(k, v);
+        }
+        for (k, v) in other.dot_palette {
+            self.dot_palette.insert(k, v);
+        }
+        if other.filters.filter_type.is_some() {
+            self.filters.filter_type = other.filters.filter_type;
+        }
+        if other.filters.status.is_some() {
+            self.filters.status = other.filters.status;
+        }
+        if other.filters.priority.is_some() {
+            self.filters.priority = other.filters.priority;
+        }
+    }
+
+    /// Remove a single inherited key, e.g. `%unset status_aliases.inbox`.
+    fn unset(&mut self, key: &str) {
+        if let Some((section, field)) = key.split_once('.') {
+            match section {
+                "status_aliases" => {
+                    self.status_aliases.remove(field);
+                }
+                "field_map" => {
+                    self.field_map.remove(field);
+                }
+                "dot_palette" => {
+                    self.dot_palette.remove(field);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn config_from_toml(value: &TomlValue) -> FastIndexerConfig {
+    let mut config = FastIndexerConfig::default();
+    let table = match value.as_table() {
+        Some(t) => t,
+        None => return config,
+    };
+
+    if let Some(aliases) = table.get("status_aliases").and_then(|v| v.as_table()) {
+        for (k, v) in aliases {
+            if let Some(s) = v.as_str() {
+                config.status_aliases.insert(k.clone(), s.to_string());
+            }
+        }
+    }
+    if let Some(map) = table.get("field_map").and_then(|v| v.as_table()) {
+        for (k, v) in map {
+            if let Some(s) = v.as_str() {
+                config.field_map.insert(k.clone(), s.to_string());
+            }
+        }
+    }
+    if let Some(palette) = table.get("dot_palette").and_then(|v| v.as_table()) {
+        for (k, v) in palette {
+            if let Some(s) = v.as_str() {
+                config.dot_palette.insert(k.clone(), s.to_string());
+            }
+        }
+    }
+    if let Some(filters) = table.get("filters").and_then(|v| v.as_table()) {
+        if let Some(arr) = filters.get("filter_type").and_then(|v| v.as_array()) {
+            config.filters.filter_type =
+                Some(arr.iter().filter_map(|v| v.as_str().map(String::from)).collect());
+        }
+        if let Some(arr) = filters.get("status").and_then(|v| v.as_array()) {
+            config.filters.status =
+                Some(arr.iter().filter_map(|v| v.as_str().map(String::from)).collect());
+        }
+        if let Some(arr) = filters.get("priority").and_then(|v| v.as_array()) {
+            config.filters.priority =
+                Some(arr.iter().filter_map(|v| v.as_integer().map(|i| i as i32)).collect());
+        }
+    }
+
+    config
+}
+
+/// Load a `.fastindexer.toml`-style config, resolving `%include <path>` directives
+/// (merged in before the current file, so the current file's keys win) and
+/// `%unset <key>` directives (drops a key inherited from an include) line by line
+/// before handing the remainder to the TOML parser. Resolution is strictly
+/// last-writer-wins with child-over-parent precedence.
+fn load_config_layer(path: &Path, seen: &mut HashSet<PathBuf>) -> FastIndexerConfig {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if !seen.insert(canonical) {
+        // Already loaded this file on the current include chain; don't loop forever.
+        return FastIndexerConfig::default();
+    }
+
+    let raw = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(_) => return FastIndexerConfig::default(),
+    };
+
+    let mut config = FastIndexerConfig::default();
+    let mut own_lines = String::new();
+    let mut unset_keys = Vec::new();
+
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("%include ") {
+            let include_path = path.parent().unwrap_or_else(|| Path::new(".")).join(rest.trim());
+            config.merge_from(load_config_layer(&include_path, seen));
+        } else if let Some(rest) = trimmed.strip_prefix("%unset ") {
+            unset_keys.push(rest.trim().to_string());
+        } else {
+            own_lines.push_str(line);
+            own_lines.push('\n');
+        }
+    }
+
+    let own_toml: TomlValue = own_lines.parse().unwrap_or(TomlValue::Table(Default::default()));
+    config.merge_from(config_from_toml(&own_toml));
+
+    for key in &unset_keys {
+        config.unset(key);
+    }
+
+    config
+}
+
+/// Load the config at `path` if it exists, otherwise fall back to built-in
+/// defaults (empty maps; `resolve_status_alias` still has its hardcoded fallback).
+fn load_config(path: &Path) -> FastIndexerConfig {
+    if !path.exists() {
+        return FastIndexerConfig::default();
+    }
+    let mut seen = HashSet::new();
+    load_config_layer(path, &mut seen)
+}
+
+/// Incremental parse cache manifest filename, written under `~/.aops/index/`
+/// (same home alongside the default graph output) rather than per-vault, so
+/// it survives across scan roots like Mercurial's dirstate survives checkouts.
+const CACHE_FILENAME: &str = "parse-cache.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CacheEntry {
+    /// mtime (unix seconds) + file size; cheap enough to stat on every run
+    mtime_secs: u64,
+    size: u64,
+    /// Content hash, used to confirm a real change when mtime is ambiguous
+    /// (see `is_cache_hit`). Reuses the crate's existing md5 dependency rather
+    /// than pulling in a new hashing crate for this alone.
+    content_hash: String,
+    /// (path, mtime_secs, size) of the `inherit:`-referenced defaults file at
+    /// parse time, if this task has one. A cache hit on the task's own file
+    /// stat isn't enough if the shared defaults file it inherits from has
+    /// since changed (see `inherit_source_unchanged`).
+    #[serde(default)]
+    inherit_source: Option<(String, u64, u64)>,
+    data: FileData,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct ParseCache {
+    entries: HashMap<String, CacheEntry>,
+}
+
+struct FileStat {
+    mtime_secs: u64,
+    size: u64,
+}
+
+fn stat_file(path: &Path) -> Option<FileStat> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime_secs = meta.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+    Some(FileStat { mtime_secs, size: meta.len() })
+}
+
+fn content_hash(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    Some(format!("{:x}", md5::compute(&bytes)))
+}
+
+/// A file is unchanged if its (mtime, size) matches the cached record. But a
+/// same-second rewrite wouldn't move a one-second-resolution mtime, so when
+/// the file's mtime is within the last couple of seconds of "now" we can't
+/// fully trust it and fall back to confirming with a content hash -- this is
+/// the same ambiguous-mtime problem Mercurial's dirstate guards against.
+fn is_cache_hit(stat: &FileStat, entry: &CacheEntry, path: &Path, now_secs: u64) -> bool {
+    if stat.size != entry.size || stat.mtime_secs != entry.mtime_secs {
+        return false;
+    }
+    if now_secs.saturating_sub(stat.mtime_secs) < 2 {
+        return content_hash(path).as_deref() == Some(entry.content_hash.as_str());
+    }
+    true
+}
+
+/// A cache entry's `inherit_source`, if any, must also still match its
+/// recorded (mtime, size) -- otherwise the task's own unchanged stat would
+/// mask a stale inherited field (see `inherit_source_path`).
+fn inherit_source_unchanged(entry: &CacheEntry) -> bool {
+    match &entry.inherit_source {
+        None => true,
+        Some((path, mtime_secs, size)) => stat_file(Path::new(path))
+            .is_some_and(|s| s.mtime_secs == *mtime_secs && s.size == *size),
+    }
+}
+
+fn parse_cache_path() -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(home).join(".aops").join("index").join(CACHE_FILENAME)
+}
+
+fn load_parse_cache(path: &Path) -> ParseCache {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_parse_cache(path: &Path, cache: &ParseCache) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    let json = serde_json::to_string_pretty(cache)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Node {
     id: String,
@@ -111,15 +435,12 @@ struct Node {
     #[serde(skip_serializing_if = "Option::is_none")]
     soft_depends_on: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-<<<<<<< HEAD
     blocks: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     soft_blocks: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     children: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-=======
->>>>>>> 54a3d25 (chore: ensure custodiet.md is present)
     assignee: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     complexity: Option<String>,
@@ -177,6 +498,16 @@ struct McpIndexEntry {
     blocks: Vec<String>,        // Computed: inverse of depends_on
     soft_depends_on: Vec<String>,
     soft_blocks: Vec<String>,   // Computed: inverse of soft_depends_on (informational, not blocking)
+    /// Nearest incomplete ancestors in the `depends_on` chain: the transitive
+    /// hard dependencies actually responsible for this task not being ready
+    /// (skipping over any intermediate dep that is itself `done`/`cancelled`
+    /// but whose own dependency isn't). Empty unless this task is `blocked`.
+    #[serde(default)]
+    blocked_by: Vec<String>,
+    /// Precomputed CharBag over the lowercased `id` + `title`, for the cheap
+    /// first-pass reject in `fuzzy_query_mcp_index` (see `CharBag`).
+    #[serde(default)]
+    char_bag: u64,
     depth: i32,
     leaf: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -201,13 +532,18 @@ struct McpIndex {
     roots: Vec<String>,
     ready: Vec<String>,
     blocked: Vec<String>,
+    /// Dependency cycles found in the hard-dependency graph, each as an ordered
+    /// chain `[a, b, c, a]`. Non-empty means some tasks can never become ready.
+    #[serde(default)]
+    cycles: Vec<Vec<String>>,
 }
 
-#[derive(Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 struct FileData {
     path: PathBuf,
     id: String,
     label: String,
+    content: String,
     tags: Vec<String>,
     raw_links: Vec<String>,
     permalinks: Vec<String>, // For ID resolution (filename, permalink key, etc)
@@ -221,6 +557,7 @@ struct FileData {
     soft_depends_on: Vec<String>,
     children: Vec<String>,
     blocks: Vec<String>,
+    soft_blocks: Vec<String>,
     project: Option<String>,
     due: Option<String>,
     depth: i32,
@@ -239,8 +576,12 @@ fn compute_id(path: &Path) -> String {
 }
 
 /// Resolve status aliases for backwards compatibility.
-/// Maps legacy statuses like "inbox" to canonical values like "active".
-fn resolve_status_alias(status: &str) -> &str {
+/// Config-declared `[status_aliases]` take precedence; built-in aliases (e.g.
+/// "inbox" -> "active") are the fallback when no config covers a status.
+fn resolve_status_alias(status: &str, aliases: &HashMap<String, String>) -> String {
+    if let Some(mapped) = aliases.get(status) {
+        return mapped.clone();
+    }
     match status {
         "inbox" => "active",
         "todo" => "active",
@@ -253,26 +594,48 @@ fn resolve_status_alias(status: &str) -> &str {
         "closed" => "done",
         other => other,
     }
+    .to_string()
+}
+
+/// Look up a canonical frontmatter field, honoring a configured `[field_map]`
+/// remap (a source key mapped onto `canonical_key`) before falling back to the
+/// field's own name.
+fn fm_lookup<'a>(
+    frontmatter: &'a Option<serde_json::Value>,
+    field_map: &HashMap<String, String>,
+    canonical_key: &str,
+) -> Option<&'a serde_json::Value> {
+    let fm = frontmatter.as_ref()?;
+    for (source_key, target_field) in field_map {
+        if target_field == canonical_key {
+            if let Some(v) = fm.get(source_key) {
+                return Some(v);
+            }
+        }
+    }
+    fm.get(canonical_key)
 }
 
-fn extract_tags(frontmatter: &Option<serde_json::Value>, content: &str) -> Vec<String> {
+fn extract_tags(
+    frontmatter: &Option<serde_json::Value>,
+    field_map: &HashMap<String, String>,
+    content: &str,
+) -> Vec<String> {
     let mut tags = HashSet::new();
 
     // 1. Frontmatter tags
-    if let Some(fm) = frontmatter {
-        if let Some(tag_val) = fm.get("tags") {
-            if let Some(arr) = tag_val.as_array() {
-                for t in arr {
-                    if let Some(s) = t.as_str() {
-                        tags.insert(s.to_string());
-                    }
-                }
-            } else if let Some(s) = tag_val.as_str() {
-                // Handle comma separated
-                for part in s.split(',') {
-                    tags.insert(part.trim().to_string());
+    if let Some(tag_val) = fm_lookup(frontmatter, field_map, "tags") {
+        if let Some(arr) = tag_val.as_array() {
+            for t in arr {
+                if let Some(s) = t.as_str() {
+                    tags.insert(s.to_string());
                 }
             }
+        } else if let Some(s) = tag_val.as_str() {
+            // Handle comma separated
+            for part in s.split(',') {
+                tags.insert(part.trim().to_string());
+            }
         }
     }
 
@@ -289,10 +652,13 @@ fn extract_tags(frontmatter: &Option<serde_json::Value>, content: &str) -> Vec<S
     tags.into_iter().collect()
 }
 
-/// Helper to parse a string array from frontmatter
-fn parse_string_array_from_fm(fm_data: &Option<serde_json::Value>, key: &str) -> Vec<String> {
-    fm_data.as_ref()
-        .and_then(|fm| fm.get(key))
+/// Helper to parse a string array from frontmatter, honoring `[field_map]`
+fn parse_string_array_from_fm(
+    fm_data: &Option<serde_json::Value>,
+    field_map: &HashMap<String, String>,
+    key: &str,
+) -> Vec<String> {
+    fm_lookup(fm_data, field_map, key)
         .and_then(|v| v.as_array())
         .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
         .unwrap_or_default()
@@ -313,25 +679,110 @@ fn vec_to_option<T>(vec: Vec<T>) -> Option<Vec<T>> {
     }
 }
 
-fn parse_file(path: PathBuf) -> Option<FileData> {
+/// Frontmatter field naming a shared-defaults file this task inherits
+/// project-level metadata from, e.g. `inherit: _project.md`, resolved
+/// relative to the task's own directory.
+const INHERIT_FIELD: &str = "inherit";
+/// Frontmatter field listing canonical keys this task opts out of
+/// inheriting, Mercurial-`%unset`-style, e.g. `unset: [assignee]`.
+const UNSET_FIELD: &str = "unset";
+
+/// Parse just the frontmatter of a file at `path`, for reading a shared
+/// `inherit:` defaults file without pulling in the rest of `parse_file`.
+fn read_frontmatter(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    let result = Matter::<YAML>::new().parse(&content);
+    result.data.as_ref().and_then(|d| d.deserialize::<serde_json::Value>().ok())
+}
+
+/// Resolve a task's `inherit:` frontmatter field (if any) to the absolute path
+/// of the shared defaults file it names, without reading that file's own
+/// contents. Used by the parse cache (see `CacheEntry::inherit_source`) to
+/// know which extra file's mtime/size to watch for staleness, since a task's
+/// own (mtime, size, content_hash) unchanged doesn't mean its *inherited*
+/// fields are still current.
+fn inherit_source_path(path: &Path) -> Option<PathBuf> {
+    let serde_json::Value::Object(own) = read_frontmatter(path)? else {
+        return None;
+    };
+    let inherit_rel = own.get(INHERIT_FIELD).and_then(|v| v.as_str())?;
+    Some(path.parent().unwrap_or_else(|| Path::new(".")).join(inherit_rel))
+}
+
+/// Merge the shared defaults named by a task's `inherit:` frontmatter field
+/// into its own frontmatter, Mercurial layered-config style: an inherited key
+/// only fills a field the task left empty, `unset:` opts the task out of
+/// specific inherited keys, and array-valued fields (e.g. `tags`) union with
+/// the inherited value instead of being overwritten. Returns `fm_data`
+/// unchanged if there's no `inherit:` field or the referenced file can't be
+/// read/parsed. Only resolves one level -- the included file's own `inherit`
+/// (if any) is not itself followed, since this models a single shared
+/// project-defaults file, not a cascading chain.
+fn resolve_inherited_frontmatter(
+    path: &Path,
+    fm_data: Option<serde_json::Value>,
+) -> Option<serde_json::Value> {
+    let Some(serde_json::Value::Object(own)) = &fm_data else {
+        return fm_data;
+    };
+    let Some(inherit_rel) = own.get(INHERIT_FIELD).and_then(|v| v.as_str()) else {
+        return fm_data;
+    };
+    let inherit_path = path.parent().unwrap_or_else(|| Path::new(".")).join(inherit_rel);
+    let Some(serde_json::Value::Object(defaults)) = read_frontmatter(&inherit_path) else {
+        return fm_data;
+    };
+
+    let unset_keys: HashSet<&str> = own
+        .get(UNSET_FIELD)
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    let mut merged = own.clone();
+    for (key, default_value) in &defaults {
+        if key == INHERIT_FIELD || key == UNSET_FIELD || unset_keys.contains(key.as_str()) {
+            continue;
+        }
+        match merged.get(key).cloned() {
+            None | Some(serde_json::Value::Null) => {
+                merged.insert(key.clone(), default_value.clone());
+            }
+            Some(serde_json::Value::Array(mut union)) => {
+                if let Some(default_arr) = default_value.as_array() {
+                    for v in default_arr {
+                        if !union.contains(v) {
+                            union.push(v.clone());
+                        }
+                    }
+                }
+                merged.insert(key.clone(), serde_json::Value::Array(union));
+            }
+            Some(_) => {} // task already set this field explicitly; keep its value
+        }
+    }
+
+    Some(serde_json::Value::Object(merged))
+}
+
+fn parse_file(path: PathBuf, config: &FastIndexerConfig) -> Option<FileData> {
     let content = fs::read_to_string(&path).ok()?;
     let matter = Matter::<YAML>::new();
     let result = matter.parse(&content);
 
-    // Frontmatter data
+    // Frontmatter data, with any `inherit:`-referenced project defaults merged in.
     let fm_data = result.data.as_ref().map(|d| d.deserialize::<serde_json::Value>().ok()).flatten();
+    let fm_data = resolve_inherited_frontmatter(&path, fm_data);
 
     // 1. Label/Title
     let mut label = path.file_stem()?.to_string_lossy().to_string();
-    if let Some(ref fm) = fm_data {
-        if let Some(title) = fm.get("title").and_then(|v| v.as_str()) {
-            label = title.to_string();
-        }
+    if let Some(title) = fm_lookup(&fm_data, &config.field_map, "title").and_then(|v| v.as_str()) {
+        label = title.to_string();
     }
     // Fallback to H1 if no FM title? (Simplification: skipping H1 parse for speed/robustness unless needed)
 
     // 2. Tags
-    let tags = extract_tags(&fm_data, &result.content);
+    let tags = extract_tags(&fm_data, &config.field_map, &result.content);
 
     // 3. Permalinks / Resolution Keys
     let mut permalinks = Vec::new();
@@ -383,31 +834,35 @@ fn parse_file(path: PathBuf) -> Option<FileData> {
         }
     }
 
-    // Extract task-related frontmatter fields
-    let node_type = fm_data.as_ref().and_then(|fm| fm.get("type").and_then(|v| v.as_str()).map(String::from));
-    // Resolve status aliases (e.g., "inbox" -> "active") for backwards compatibility
-    let status = fm_data.as_ref().and_then(|fm| fm.get("status").and_then(|v| v.as_str()).map(|s| resolve_status_alias(s).to_string()));
-    let priority = fm_data.as_ref().and_then(|fm| fm.get("priority").and_then(|v| v.as_i64()).map(|v| v as i32));
-    let order = fm_data.as_ref().and_then(|fm| fm.get("order").and_then(|v| v.as_i64()).map(|v| v as i32)).unwrap_or(0);
-    let parent = fm_data.as_ref().and_then(|fm| fm.get("parent").and_then(|v| v.as_str()).map(String::from));
-    let depends_on = parse_string_array_from_fm(&fm_data, "depends_on");
-    let soft_depends_on = parse_string_array_from_fm(&fm_data, "soft_depends_on");
-    let children = parse_string_array_from_fm(&fm_data, "children");
-    let blocks = parse_string_array_from_fm(&fm_data, "blocks");
-    let soft_blocks = parse_string_array_from_fm(&fm_data, "soft_blocks");
-    let project = fm_data.as_ref().and_then(|fm| fm.get("project").and_then(|v| v.as_str()).map(String::from));
-    let due = fm_data.as_ref().and_then(|fm| fm.get("due").and_then(|v| v.as_str()).map(String::from));
-    let depth = fm_data.as_ref().and_then(|fm| fm.get("depth").and_then(|v| v.as_i64()).map(|v| v as i32)).unwrap_or(0);
-    let leaf = fm_data.as_ref().and_then(|fm| fm.get("leaf").and_then(|v| v.as_bool())).unwrap_or(true);
+    // Extract task-related frontmatter fields, honoring the configured [field_map]
+    let field_map = &config.field_map;
+    let node_type = fm_lookup(&fm_data, field_map, "type").and_then(|v| v.as_str()).map(String::from);
+    // Resolve status aliases (config-declared first, then built-in e.g. "inbox" -> "active")
+    let status = fm_lookup(&fm_data, field_map, "status")
+        .and_then(|v| v.as_str())
+        .map(|s| resolve_status_alias(s, &config.status_aliases));
+    let priority = fm_lookup(&fm_data, field_map, "priority").and_then(|v| v.as_i64()).map(|v| v as i32);
+    let order = fm_lookup(&fm_data, field_map, "order").and_then(|v| v.as_i64()).map(|v| v as i32).unwrap_or(0);
+    let parent = fm_lookup(&fm_data, field_map, "parent").and_then(|v| v.as_str()).map(String::from);
+    let depends_on = parse_string_array_from_fm(&fm_data, field_map, "depends_on");
+    let soft_depends_on = parse_string_array_from_fm(&fm_data, field_map, "soft_depends_on");
+    let children = parse_string_array_from_fm(&fm_data, field_map, "children");
+    let blocks = parse_string_array_from_fm(&fm_data, field_map, "blocks");
+    let soft_blocks = parse_string_array_from_fm(&fm_data, field_map, "soft_blocks");
+    let project = fm_lookup(&fm_data, field_map, "project").and_then(|v| v.as_str()).map(String::from);
+    let due = fm_lookup(&fm_data, field_map, "due").and_then(|v| v.as_str()).map(String::from);
+    let depth = fm_lookup(&fm_data, field_map, "depth").and_then(|v| v.as_i64()).map(|v| v as i32).unwrap_or(0);
+    let leaf = fm_lookup(&fm_data, field_map, "leaf").and_then(|v| v.as_bool()).unwrap_or(true);
     let task_id = fm_data.as_ref().and_then(|fm| fm.get("id").and_then(|v| v.as_str()).map(String::from));
-    let assignee = fm_data.as_ref().and_then(|fm| fm.get("assignee").and_then(|v| v.as_str()).map(String::from));
-    let complexity = fm_data.as_ref().and_then(|fm| fm.get("complexity").and_then(|v| v.as_str()).map(String::from));
+    let assignee = fm_lookup(&fm_data, field_map, "assignee").and_then(|v| v.as_str()).map(String::from);
+    let complexity = fm_lookup(&fm_data, field_map, "complexity").and_then(|v| v.as_str()).map(String::from);
 
     Some(FileData {
         // Use frontmatter id as node identifier when present, fall back to path hash
         id: task_id.clone().unwrap_or_else(|| compute_id(&path)),
         path,
         label,
+        content: result.content.clone(),
         tags,
         raw_links,
         permalinks,
@@ -420,10 +875,7 @@ fn parse_file(path: PathBuf) -> Option<FileData> {
         soft_depends_on,
         children,
         blocks,
-<<<<<<< HEAD
         soft_blocks,
-=======
->>>>>>> 54a3d25 (chore: ensure custodiet.md is present)
         project,
         due,
         depth,
@@ -564,47 +1016,598 @@ fn output_graphml(graph: &Graph, path: &str) -> Result<()> {
     Ok(())
 }
 
-fn output_dot(graph: &Graph, path: &str) -> Result<()> {
+/// Look up a `dot_palette` override by key, falling back to `default` when
+/// the config doesn't cover it.
+fn palette_get<'a>(palette: &'a HashMap<String, String>, key: &str, default: &'a str) -> &'a str {
+    palette.get(key).map(String::as_str).unwrap_or(default)
+}
+
+/// Edge styling keyed by `EdgeType`, overridable per-key via `[dot_palette]`
+/// in `.fastindexer.toml` (e.g. `"edge.depends_on" = "color=\"#f00\""`):
+/// - depends_on (hard): solid arrow, red (blocking, strong visual)
+/// - soft_depends_on: dashed arrow, orange (non-blocking, subtle)
+/// - parent: thick solid line, blue (hierarchical)
+/// - link: thin dotted gray line (default, lowest visual weight)
+fn edge_style<'a>(edge_type: &EdgeType, palette: &'a HashMap<String, String>) -> &'a str {
+    let (key, default) = match edge_type {
+        EdgeType::DependsOn => ("edge.depends_on", "style=bold, color=\"#dc3545\", penwidth=2"),
+        EdgeType::SoftDependsOn => {
+            ("edge.soft_depends_on", "style=dashed, color=\"#fd7e14\", penwidth=1.5")
+        }
+        EdgeType::Parent => ("edge.parent", "style=solid, color=\"#0d6efd\", penwidth=3"),
+        EdgeType::Link => ("edge.link", "style=dotted, color=\"#adb5bd\", penwidth=1"),
+    };
+    palette_get(palette, key, default)
+}
+
+/// Node fill/border color keyed by canonical `status`, overridable via
+/// `[dot_palette]` (e.g. `"node.status.blocked" = "#ff0000"`). Unknown or
+/// missing statuses fall back to the neutral default node fill.
+fn node_status_style<'a>(status: Option<&str>, palette: &'a HashMap<String, String>) -> &'a str {
+    let (key, default) = match status {
+        Some("blocked") => ("node.status.blocked", "#f8d7da"),
+        Some("active") | Some("in_progress") => ("node.status.ready", "#d1e7dd"),
+        Some("waiting") => ("node.status.waiting", "#fff3cd"),
+        Some("done") | Some("cancelled") => ("node.status.done", "#e9ecef"),
+        _ => ("node.status.default", "#e9ecef"),
+    };
+    palette_get(palette, key, default)
+}
+
+/// Border weight by `priority` (0=critical .. 4=someday, per `FileData`),
+/// so the most urgent nodes stand out without changing their fill color.
+fn priority_penwidth(priority: Option<i32>) -> &'static str {
+    match priority {
+        Some(0) => "3",
+        Some(1) => "2",
+        _ => "1",
+    }
+}
+
+fn output_dot(graph: &Graph, path: &str, palette: &HashMap<String, String>) -> Result<()> {
     let mut dot = String::from("digraph G {\n    rankdir=TB;\n    node [shape=box, style=filled, fillcolor=\"#e9ecef\"];\n\n");
 
     for node in &graph.nodes {
         let label_escaped = node.label.replace("\"", "\\\"");
-        dot.push_str(&format!("    \"{}\" [label=\"{}\"];\n", node.id, label_escaped));
+        let fillcolor = node_status_style(node.status.as_deref(), palette);
+        let penwidth = priority_penwidth(node.priority);
+        dot.push_str(&format!(
+            "    \"{}\" [label=\"{}\", fillcolor=\"{}\", penwidth={}];\n",
+            node.id, label_escaped, fillcolor, penwidth
+        ));
     }
 
     dot.push('\n');
 
-    // Edge styling based on type:
-    // - depends_on (hard): solid arrow, color=#d63384 (blocking, strong visual)
-    // - soft_depends_on: dashed arrow, color=#6c757d (non-blocking, subtle)
-    // - parent: thick solid line, color=#0d6efd (hierarchical)
-    // - link: thin gray line (default, lowest visual weight)
     for edge in &graph.edges {
-        let style = match edge.edge_type {
-            EdgeType::DependsOn => "style=bold, color=\"#dc3545\", penwidth=2",
-            EdgeType::SoftDependsOn => "style=dashed, color=\"#6c757d\", penwidth=1.5",
-            EdgeType::Parent => "style=solid, color=\"#0d6efd\", penwidth=3",
-            EdgeType::Link => "style=dotted, color=\"#adb5bd\", penwidth=1",
-        };
+        let style = edge_style(&edge.edge_type, palette);
         dot.push_str(&format!("    \"{}\" -> \"{}\" [{}];\n", edge.source, edge.target, style));
     }
 
+    dot.push('\n');
+    dot.push_str(&dot_legend(palette));
+
     dot.push_str("}\n");
     fs::write(path, dot)?;
     Ok(())
 }
 
+/// A legend subgraph listing each edge type and node status with its color,
+/// so a rendered graph is readable without cross-referencing this source.
+fn dot_legend(palette: &HashMap<String, String>) -> String {
+    let mut legend = String::from("    subgraph cluster_legend {\n        label=\"Legend\";\n        style=dashed;\n        fontsize=10;\n        node [shape=plaintext];\n\n");
+
+    let edges = [
+        ("depends_on", EdgeType::DependsOn),
+        ("soft_depends_on", EdgeType::SoftDependsOn),
+        ("parent", EdgeType::Parent),
+        ("link", EdgeType::Link),
+    ];
+    for (name, edge_type) in edges {
+        let style = edge_style(&edge_type, palette);
+        legend.push_str(&format!(
+            "        \"legend_{name}_a\" [label=\"\"]; \"legend_{name}_b\" [label=\"{name}\"];\n        \"legend_{name}_a\" -> \"legend_{name}_b\" [{style}];\n"
+        ));
+    }
+
+    let statuses = ["blocked", "active", "waiting", "done"];
+    for status in statuses {
+        let color = node_status_style(Some(status), palette);
+        legend.push_str(&format!(
+            "        \"legend_status_{status}\" [label=\"{status}\", fillcolor=\"{color}\", style=filled];\n"
+        ));
+    }
+
+    legend.push_str("    }\n");
+    legend
+}
+
+/// Build a client-side term -> node id inverted index from labels and tags,
+/// for the instant-highlight search box in the HTML viewer.
+fn build_html_search_index(graph: &Graph) -> HashMap<String, Vec<String>> {
+    let mut index: HashMap<String, Vec<String>> = HashMap::new();
+    for node in &graph.nodes {
+        let mut terms: HashSet<String> = tokenize(&node.label).into_iter().collect();
+        if let Some(ref tags) = node.tags {
+            for tag in tags {
+                terms.extend(tokenize(tag));
+            }
+        }
+        for term in terms {
+            let ids = index.entry(term).or_default();
+            if !ids.contains(&node.id) {
+                ids.push(node.id.clone());
+            }
+        }
+    }
+    index
+}
+
+/// `serde_json::to_string` doesn't escape `/`, so a raw `</script>` inside a
+/// frontmatter string (e.g. a task titled `</script><script>...`) would
+/// otherwise close the embedding `<script>` block early and let arbitrary
+/// markup run in the viewer. Escaping `<` to its six-character JSON unicode escape keeps
+/// the JSON semantically identical (valid inside a JS string/object literal
+/// either way) while making it inert as HTML.
+fn escape_for_inline_script(json: &str) -> String {
+    json.replace('<', r"\u003c")
+}
+
+/// Self-contained interactive HTML viewer: embeds the graph JSON, a client-side
+/// search index, and a force-directed canvas renderer with live filtering, so
+/// non-technical collaborators can explore the graph without yEd/Gephi.
+fn output_html(graph: &Graph, path: &str) -> Result<()> {
+    let graph_json = escape_for_inline_script(&serde_json::to_string(graph)?);
+    let search_index_json = escape_for_inline_script(&serde_json::to_string(&build_html_search_index(graph))?);
+
+    let html = format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>fast-indexer graph browser</title>
+<style>
+  html, body {{ margin: 0; height: 100%; font-family: sans-serif; background: #fff; color: #212529; }}
+  #toolbar {{ position: fixed; top: 0; left: 0; right: 0; z-index: 1; display: flex; gap: 12px;
+              align-items: center; padding: 8px 12px; background: #f8f9fa; border-bottom: 1px solid #dee2e6; }}
+  #toolbar input, #toolbar select {{ padding: 4px 6px; }}
+  #canvas-wrap {{ position: absolute; top: 48px; left: 0; right: 0; bottom: 0; }}
+  canvas {{ width: 100%; height: 100%; display: block; }}
+  #legend {{ position: fixed; bottom: 8px; left: 8px; font-size: 12px; background: rgba(255,255,255,0.9);
+             padding: 6px 8px; border: 1px solid #dee2e6; border-radius: 4px; }}
+  #legend span {{ display: inline-block; width: 18px; border-bottom: 3px solid; margin-right: 4px; vertical-align: middle; }}
+</style>
+</head>
+<body>
+<div id="toolbar">
+  <input id="search" type="text" placeholder="Search labels and tags...">
+  <select id="status-filter"><option value="">All statuses</option></select>
+  <select id="priority-filter"><option value="">All priorities</option></select>
+  <select id="project-filter"><option value="">All projects</option></select>
+  <select id="tag-filter"><option value="">All tags</option></select>
+  <span id="count"></span>
+</div>
+<div id="canvas-wrap"><canvas id="graph"></canvas></div>
+<div id="legend">
+  <div><span style="border-color:#dc3545"></span>depends_on (hard)</div>
+  <div><span style="border-color:#6c757d; border-bottom-style:dashed"></span>soft_depends_on</div>
+  <div><span style="border-color:#0d6efd"></span>parent</div>
+  <div><span style="border-color:#adb5bd; border-bottom-style:dotted"></span>link</div>
+</div>
+<script>
+const GRAPH = {graph_json};
+const SEARCH_INDEX = {search_index_json};
+
+const EDGE_STYLE = {{
+  depends_on: {{ color: "#dc3545", width: 2, dash: [] }},
+  soft_depends_on: {{ color: "#6c757d", width: 1.5, dash: [6, 4] }},
+  parent: {{ color: "#0d6efd", width: 3, dash: [] }},
+  link: {{ color: "#adb5bd", width: 1, dash: [2, 3] }},
+}};
+const STATUS_COLOR = {{ blocked: "#dc3545", active: "#198754", in_progress: "#198754", done: "#adb5bd", cancelled: "#adb5bd" }};
+const DEFAULT_NODE_COLOR = "#e9ecef";
+
+// Simple force-directed layout (no external deps): springs along edges,
+// mutual repulsion between all nodes, settled over a fixed number of ticks.
+const nodeById = new Map();
+for (const n of GRAPH.nodes) {{
+  nodeById.set(n.id, {{ ...n, x: Math.random() * 800, y: Math.random() * 600, vx: 0, vy: 0 }});
+}}
+const nodes = Array.from(nodeById.values());
+const edges = GRAPH.edges.filter(e => nodeById.has(e.source) && nodeById.has(e.target));
+
+function simulate(ticks) {{
+  const REPULSION = 2500, SPRING = 0.02, SPRING_LEN = 120, DAMPING = 0.85;
+  for (let t = 0; t < ticks; t++) {{
+    for (const a of nodes) {{
+      let fx = 0, fy = 0;
+      for (const b of nodes) {{
+        if (a === b) continue;
+        const dx = a.x - b.x, dy = a.y - b.y;
+        const distSq = Math.max(dx * dx + dy * dy, 1);
+        const force = REPULSION / distSq;
+        fx += (dx / Math.sqrt(distSq)) * force;
+        fy += (dy / Math.sqrt(distSq)) * force;
+      }}
+      a.vx = (a.vx + fx) * DAMPING;
+      a.vy = (a.vy + fy) * DAMPING;
+    }}
+    for (const e of edges) {{
+      const a = nodeById.get(e.source), b = nodeById.get(e.target);
+      const dx = b.x - a.x, dy = b.y - a.y;
+      const dist = Math.sqrt(dx * dx + dy * dy) || 1;
+      const diff = (dist - SPRING_LEN) * SPRING;
+      const fx = (dx / dist) * diff, fy = (dy / dist) * diff;
+      a.vx += fx; a.vy += fy;
+      b.vx -= fx; b.vy -= fy;
+    }}
+    for (const n of nodes) {{ n.x += n.vx; n.y += n.vy; }}
+  }}
+}}
+simulate(300);
+
+const canvas = document.getElementById("graph");
+const ctx = canvas.getContext("2d");
+function resize() {{
+  canvas.width = canvas.clientWidth;
+  canvas.height = canvas.clientHeight;
+}}
+window.addEventListener("resize", () => {{ resize(); draw(); }});
+resize();
+
+let activeIds = null; // null = show everything
+
+function draw() {{
+  ctx.clearRect(0, 0, canvas.width, canvas.height);
+  ctx.save();
+  ctx.translate(canvas.width / 2, canvas.height / 2);
+  ctx.scale(0.7, 0.7);
+  ctx.translate(-400, -300);
+
+  for (const e of edges) {{
+    const a = nodeById.get(e.source), b = nodeById.get(e.target);
+    if (activeIds && (!activeIds.has(a.id) || !activeIds.has(b.id))) continue;
+    const style = EDGE_STYLE[e.type] || EDGE_STYLE.link;
+    ctx.beginPath();
+    ctx.setLineDash(style.dash);
+    ctx.strokeStyle = style.color;
+    ctx.lineWidth = style.width;
+    ctx.moveTo(a.x, a.y);
+    ctx.lineTo(b.x, b.y);
+    ctx.stroke();
+  }}
+  ctx.setLineDash([]);
+
+  for (const n of nodes) {{
+    if (activeIds && !activeIds.has(n.id)) continue;
+    ctx.beginPath();
+    ctx.arc(n.x, n.y, 8, 0, Math.PI * 2);
+    ctx.fillStyle = STATUS_COLOR[n.status] || DEFAULT_NODE_COLOR;
+    ctx.fill();
+    ctx.strokeStyle = "#495057";
+    ctx.stroke();
+    ctx.fillStyle = "#212529";
+    ctx.font = "11px sans-serif";
+    ctx.fillText(n.label, n.x + 10, n.y + 4);
+  }}
+  ctx.restore();
+}}
+draw();
+
+// --- Filter controls, populated from whatever values actually appear ---
+function populateSelect(id, values) {{
+  const sel = document.getElementById(id);
+  [...values].sort().forEach(v => {{
+    const opt = document.createElement("option");
+    opt.value = v; opt.textContent = v;
+    sel.appendChild(opt);
+  }});
+}}
+populateSelect("status-filter", new Set(nodes.map(n => n.status).filter(Boolean)));
+populateSelect("priority-filter", new Set(nodes.map(n => n.priority).filter(p => p !== undefined && p !== null)));
+populateSelect("project-filter", new Set(nodes.map(n => n.project).filter(Boolean)));
+populateSelect("tag-filter", new Set(nodes.flatMap(n => n.tags || [])));
+
+function applyFilters() {{
+  const status = document.getElementById("status-filter").value;
+  const priority = document.getElementById("priority-filter").value;
+  const project = document.getElementById("project-filter").value;
+  const tag = document.getElementById("tag-filter").value;
+  const query = document.getElementById("search").value.trim().toLowerCase();
+
+  let matches = nodes;
+  if (status) matches = matches.filter(n => n.status === status);
+  if (priority) matches = matches.filter(n => String(n.priority) === priority);
+  if (project) matches = matches.filter(n => n.project === project);
+  if (tag) matches = matches.filter(n => (n.tags || []).includes(tag));
+  if (query) {{
+    const hitIds = new Set();
+    for (const term of Object.keys(SEARCH_INDEX)) {{
+      if (term.includes(query)) SEARCH_INDEX[term].forEach(id => hitIds.add(id));
+    }}
+    matches = matches.filter(n => hitIds.has(n.id));
+  }}
+
+  const anyFilterActive = status || priority || project || tag || query;
+  activeIds = anyFilterActive ? new Set(matches.map(n => n.id)) : null;
+  document.getElementById("count").textContent = anyFilterActive
+    ? `${{matches.length}} / ${{nodes.length}} nodes`
+    : `${{nodes.length}} nodes`;
+  draw();
+}}
+["search", "status-filter", "priority-filter", "project-filter", "tag-filter"].forEach(id => {{
+  document.getElementById(id).addEventListener("input", applyFilters);
+}});
+applyFilters();
+</script>
+</body>
+</html>
+"##,
+        graph_json = graph_json,
+        search_index_json = search_index_json,
+    );
+
+    fs::write(path, html)?;
+    Ok(())
+}
+
+/// Topologically sort the hard-dependency (`depends_on`) graph with Kahn's
+/// algorithm, restricted to edges between tasks that both exist in `entries`.
+/// Returns the topo order (tasks with no unmet deps first) plus every node left
+/// over once no more zero-in-degree nodes remain -- i.e. the cycle candidates.
+fn kahn_topo_order(entries: &HashMap<String, McpIndexEntry>) -> (Vec<String>, HashSet<String>) {
+    let mut in_degree: HashMap<String, usize> = entries.keys().map(|k| (k.clone(), 0)).collect();
+    let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (tid, entry) in entries {
+        for dep_id in &entry.depends_on {
+            if entries.contains_key(dep_id) {
+                *in_degree.get_mut(tid).unwrap() += 1;
+                dependents.entry(dep_id.clone()).or_default().push(tid.clone());
+            }
+        }
+    }
+
+    let mut queue: Vec<String> = in_degree.iter().filter(|(_, &d)| d == 0).map(|(k, _)| k.clone()).collect();
+    queue.sort(); // deterministic seed order
+    let mut queue: std::collections::VecDeque<String> = queue.into();
+
+    let mut order = Vec::new();
+    while let Some(tid) = queue.pop_front() {
+        order.push(tid.clone());
+        if let Some(deps) = dependents.get(&tid) {
+            let mut newly_ready: Vec<String> = Vec::new();
+            for dependent in deps {
+                let degree = in_degree.get_mut(dependent).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    newly_ready.push(dependent.clone());
+                }
+            }
+            newly_ready.sort();
+            queue.extend(newly_ready);
+        }
+    }
+
+    let processed: HashSet<String> = order.iter().cloned().collect();
+    let unresolved: HashSet<String> = entries.keys().filter(|k| !processed.contains(*k)).cloned().collect();
+    (order, unresolved)
+}
+
+/// Find the distinct cycles among `candidates` via DFS with an explicit stack
+/// (three-color: white = unvisited, gray = on the current path, black = fully
+/// explored), not the call stack, so this terminates rather than overflowing
+/// on a single large cycle -- same reasoning as `detect_cycles_iterative`. A
+/// gray-to-gray edge is a back-edge; unwind the stack from the gray target to
+/// the current node to reconstruct the cycle.
+fn find_cycles(entries: &HashMap<String, McpIndexEntry>, candidates: &HashSet<String>) -> Vec<Vec<String>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Color {
+        White,
+        Gray,
+        Black,
+    }
+
+    fn candidate_deps<'a>(node: &str, entries: &'a HashMap<String, McpIndexEntry>, candidates: &HashSet<String>) -> Vec<&'a String> {
+        entries
+            .get(node)
+            .map(|entry| entry.depends_on.iter().filter(|dep| candidates.contains(*dep)).collect())
+            .unwrap_or_default()
+    }
+
+    let mut color: HashMap<String, Color> = candidates.iter().map(|k| (k.clone(), Color::White)).collect();
+    let mut cycles: Vec<Vec<String>> = Vec::new();
+    let mut seen_cycles: HashSet<Vec<String>> = HashSet::new();
+
+    let mut ordered_candidates: Vec<&String> = candidates.iter().collect();
+    ordered_candidates.sort(); // deterministic traversal order
+
+    for start in ordered_candidates {
+        if color.get(start).copied() != Some(Color::White) {
+            continue;
+        }
+
+        let mut stack: Vec<(String, usize)> = vec![(start.clone(), 0)];
+        color.insert(start.clone(), Color::Gray);
+
+        while let Some((node, child_idx)) = stack.last().cloned() {
+            let children = candidate_deps(&node, entries, candidates);
+            if child_idx >= children.len() {
+                color.insert(node, Color::Black);
+                stack.pop();
+                continue;
+            }
+
+            stack.last_mut().unwrap().1 += 1;
+            let child = children[child_idx].clone();
+
+            match color.get(&child).copied().unwrap_or(Color::Black) {
+                Color::White => {
+                    color.insert(child.clone(), Color::Gray);
+                    stack.push((child, 0));
+                }
+                Color::Gray => {
+                    if let Some(pos) = stack.iter().position(|(n, _)| n == &child) {
+                        let mut cycle: Vec<String> = stack[pos..].iter().map(|(n, _)| n.clone()).collect();
+                        cycle.push(child);
+                        let mut key = cycle.clone();
+                        key.sort();
+                        if seen_cycles.insert(key) {
+                            cycles.push(cycle);
+                        }
+                    }
+                }
+                Color::Black => {}
+            }
+        }
+    }
+
+    cycles
+}
+
+/// Iterative three-color DFS over a plain `id -> [id]` adjacency map: white =
+/// unvisited, gray = on the current path (tracked via an explicit stack, not
+/// the call stack), black = fully explored. A gray-to-gray edge is a
+/// back-edge; unwind the stack from the gray node to reconstruct the cycle.
+/// Used to fail fast on `depends_on`/`parent` cycles before a graph is emitted.
+fn detect_cycles_iterative(adjacency: &HashMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Color {
+        White,
+        Gray,
+        Black,
+    }
+
+    let mut color: HashMap<String, Color> = adjacency.keys().map(|k| (k.clone(), Color::White)).collect();
+    let mut cycles: Vec<Vec<String>> = Vec::new();
+    let mut seen_cycles: HashSet<Vec<String>> = HashSet::new();
+
+    let mut start_nodes: Vec<&String> = adjacency.keys().collect();
+    start_nodes.sort(); // deterministic traversal order
+
+    for start in start_nodes {
+        if color.get(start).copied() != Some(Color::White) {
+            continue;
+        }
+
+        let mut stack: Vec<(String, usize)> = vec![(start.clone(), 0)];
+        color.insert(start.clone(), Color::Gray);
+
+        while let Some((node, child_idx)) = stack.last().cloned() {
+            let children = adjacency.get(&node).map(Vec::as_slice).unwrap_or(&[]);
+            if child_idx >= children.len() {
+                color.insert(node, Color::Black);
+                stack.pop();
+                continue;
+            }
+
+            stack.last_mut().unwrap().1 += 1;
+            let child = children[child_idx].clone();
+
+            match color.get(&child).copied().unwrap_or(Color::Black) {
+                Color::White => {
+                    color.insert(child.clone(), Color::Gray);
+                    stack.push((child, 0));
+                }
+                Color::Gray => {
+                    if let Some(pos) = stack.iter().position(|(n, _)| n == &child) {
+                        let mut cycle: Vec<String> = stack[pos..].iter().map(|(n, _)| n.clone()).collect();
+                        cycle.push(child);
+                        let mut key = cycle.clone();
+                        key.sort();
+                        if seen_cycles.insert(key) {
+                            cycles.push(cycle);
+                        }
+                    }
+                }
+                Color::Black => {}
+            }
+        }
+    }
+
+    cycles
+}
+
+/// Walk the `depends_on` chain from `tid` and collect the nearest ancestors
+/// actually responsible for it being blocked: a dep whose own status isn't
+/// done/cancelled is reported directly; a dep that claims completion but
+/// isn't transitively complete (per `effectively_complete`) is skipped over
+/// in favor of its own unmet deps, so `done`-but-unsatisfied busywork doesn't
+/// hide the real cause further up the chain. A missing/unresolved dep id is
+/// reported as-is since there's nothing further to walk into.
+fn nearest_incomplete_ancestors(
+    tid: &str,
+    entries: &HashMap<String, McpIndexEntry>,
+    completed_statuses: &HashSet<&str>,
+    effectively_complete: &HashMap<String, bool>,
+) -> Vec<String> {
+    fn collect(
+        dep: &str,
+        entries: &HashMap<String, McpIndexEntry>,
+        completed_statuses: &HashSet<&str>,
+        effectively_complete: &HashMap<String, bool>,
+        seen: &mut HashSet<String>,
+        result: &mut Vec<String>,
+    ) {
+        if !seen.insert(dep.to_string()) {
+            return; // already visited on this walk (cycle guard)
+        }
+        let Some(entry) = entries.get(dep) else {
+            result.push(dep.to_string());
+            return;
+        };
+        if !completed_statuses.contains(entry.status.as_str()) {
+            result.push(dep.to_string());
+            return;
+        }
+        if !effectively_complete.get(dep).copied().unwrap_or(false) {
+            for grandparent in &entry.depends_on {
+                collect(grandparent, entries, completed_statuses, effectively_complete, seen, result);
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+    if let Some(entry) = entries.get(tid) {
+        for dep in &entry.depends_on {
+            collect(dep, entries, completed_statuses, effectively_complete, &mut seen, &mut result);
+        }
+    }
+    result
+}
+
+/// Count of every task transitively unblocked by `tid` (its reachable set over
+/// `blocks`, the inverse of `depends_on`), used to prioritize ready tasks that
+/// free up the most downstream work.
+fn downstream_count(tid: &str, entries: &HashMap<String, McpIndexEntry>) -> usize {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut stack = vec![tid.to_string()];
+    while let Some(node) = stack.pop() {
+        if let Some(entry) = entries.get(&node) {
+            for blocked_id in &entry.blocks {
+                if entries.contains_key(blocked_id) && seen.insert(blocked_id.clone()) {
+                    stack.push(blocked_id.clone());
+                }
+            }
+        }
+    }
+    seen.len()
+}
+
 /// Build MCP task index from parsed task files.
 ///
 /// This produces the exact schema expected by tasks_server.py:
 /// - version: 2
 /// - generated: ISO timestamp
 /// - tasks: {task_id: {id, title, type, status, priority, order, parent, children, depends_on,
-///   blocks, depth, leaf, project, path, due, tags, assignee, complexity}}
+///   blocks, blocked_by, char_bag, depth, leaf, project, path, due, tags, assignee, complexity}}
 /// - by_project: {project: [task_ids]}
 /// - roots: [task_ids with no parent]
-/// - ready: [leaf tasks with no unmet deps and status active]
-/// - blocked: [tasks with unmet deps or status blocked]
+/// - ready: [leaf tasks with every transitive hard dependency completed and status active]
+/// - blocked: [tasks with an incomplete transitive hard dependency or status blocked;
+///   each entry's `blocked_by` names the nearest incomplete ancestors]
+/// - cycles: [hard-dependency cycles, each as a chain `[a, b, c, a]`]
 ///
 /// ## Metadata Fields
 /// - project: Project context (from "project" field)
@@ -622,6 +1625,8 @@ fn build_mcp_index(files: &[FileData], data_root: &Path) -> McpIndex {
                 .map(|p| p.to_string_lossy().to_string())
                 .unwrap_or_else(|_| f.path.to_string_lossy().to_string());
 
+            let char_bag = CharBag::from_str(&format!("{} {}", tid, f.label)).0;
+
             entries.insert(tid.clone(), McpIndexEntry {
                 id: tid.clone(),
                 title: f.label.clone(),
@@ -635,6 +1640,8 @@ fn build_mcp_index(files: &[FileData], data_root: &Path) -> McpIndex {
                 blocks: f.blocks.clone(),
                 soft_depends_on: f.soft_depends_on.clone(),
                 soft_blocks: f.soft_blocks.clone(),
+                blocked_by: Vec::new(),
+                char_bag,
                 depth: f.depth,
                 leaf: f.leaf,
                 project: f.project.clone(),
@@ -771,13 +1778,36 @@ fn build_mcp_index(files: &[FileData], data_root: &Path) -> McpIndex {
 
     // Compute ready and blocked
     let completed_statuses: HashSet<&str> = ["done", "cancelled"].into_iter().collect();
-    let completed_ids: HashSet<String> = entries.iter()
-        .filter(|(_, e)| completed_statuses.contains(e.status.as_str()))
-        .map(|(tid, _)| tid.clone())
-        .collect();
+
+    // Topologically sort depends_on so malformed (cyclic) graphs surface explicitly
+    // instead of silently mis-classifying nodes as ready, and so ready can be
+    // ordered by how much downstream work each task unblocks.
+    let (topo_order, cycle_candidates) = kahn_topo_order(&entries);
+    let cycles = if cycle_candidates.is_empty() {
+        Vec::new()
+    } else {
+        find_cycles(&entries, &cycle_candidates)
+    };
+
+    // Fixpoint over topo order: a task is only *effectively* complete if its
+    // own status is done/cancelled AND every hard dependency is itself
+    // effectively complete, so a `done` task sitting on top of an unmet
+    // dependency can't make everything above it look ready. Cycle-candidate
+    // nodes never resolve a value here and default to incomplete below.
+    let mut effectively_complete: HashMap<String, bool> = HashMap::new();
+    for tid in &topo_order {
+        let entry = &entries[tid];
+        let own_done = completed_statuses.contains(entry.status.as_str());
+        let deps_done = entry
+            .depends_on
+            .iter()
+            .all(|d| effectively_complete.get(d).copied().unwrap_or(false));
+        effectively_complete.insert(tid.clone(), own_done && deps_done);
+    }
 
     let mut ready: Vec<String> = Vec::new();
     let mut blocked: Vec<String> = Vec::new();
+    let mut blocked_by_updates: Vec<(String, Vec<String>)> = Vec::new();
 
     for (tid, entry) in &entries {
         // Skip completed tasks
@@ -785,13 +1815,11 @@ fn build_mcp_index(files: &[FileData], data_root: &Path) -> McpIndex {
             continue;
         }
 
-        // Check if blocked
-        let unmet_deps: Vec<&String> = entry.depends_on.iter()
-            .filter(|d| !completed_ids.contains(*d))
-            .collect();
+        let blockers = nearest_incomplete_ancestors(tid, &entries, &completed_statuses, &effectively_complete);
 
-        if !unmet_deps.is_empty() || entry.status == "blocked" {
+        if !blockers.is_empty() || entry.status == "blocked" {
             blocked.push(tid.clone());
+            blocked_by_updates.push((tid.clone(), blockers));
         } else if entry.leaf && entry.status == "active" {
             // Learn tasks are observational, not actionable - exclude from ready
             if entry.task_type != "learn" {
@@ -800,11 +1828,20 @@ fn build_mcp_index(files: &[FileData], data_root: &Path) -> McpIndex {
         }
     }
 
-    // Sort ready by priority, order, title
+    for (tid, blockers) in blocked_by_updates {
+        if let Some(entry) = entries.get_mut(&tid) {
+            entry.blocked_by = blockers;
+        }
+    }
+
+    // Sort ready by downstream impact first (unblocks the most work), then
+    // priority, order, title for stable tie-breaking.
     ready.sort_by(|a, b| {
         let ea = entries.get(a).unwrap();
         let eb = entries.get(b).unwrap();
-        (ea.priority, ea.order, &ea.title).cmp(&(eb.priority, eb.order, &eb.title))
+        let da = downstream_count(a, &entries);
+        let db = downstream_count(b, &entries);
+        db.cmp(&da).then((ea.priority, ea.order, &ea.title).cmp(&(eb.priority, eb.order, &eb.title)))
     });
 
     McpIndex {
@@ -815,6 +1852,7 @@ fn build_mcp_index(files: &[FileData], data_root: &Path) -> McpIndex {
         roots,
         ready,
         blocked,
+        cycles,
     }
 }
 
@@ -825,16 +1863,434 @@ fn output_mcp_index(files: &[FileData], path: &str, data_root: &Path) -> Result<
     Ok(())
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    let root = Path::new(&args.root).canonicalize()?;
+// Fuzzy task lookup: a CharBag per task (id + title) lets `fuzzy_query_mcp_index`
+// cheaply reject candidates before running the more expensive subsequence scorer,
+// so interactive pickers stay responsive across thousands of notes.
+
+/// Lowercased-character presence bit-set over `[0-9a-z]` (36 bits of a u64).
+/// Any other byte is ignored rather than widening the set, since punctuation
+/// isn't useful signal for a "does this title plausibly contain my query"
+/// pre-filter. `contains` is a single AND+compare: `self` can only match a
+/// query whose CharBag is a subset of its own.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct CharBag(u64);
+
+impl CharBag {
+    fn bit_for(c: char) -> Option<u32> {
+        let lower = c.to_ascii_lowercase();
+        match lower {
+            '0'..='9' => Some(lower as u32 - '0' as u32),
+            'a'..='z' => Some(10 + (lower as u32 - 'a' as u32)),
+            _ => None,
+        }
+    }
+
+    fn from_str(s: &str) -> Self {
+        let mut bits: u64 = 0;
+        for c in s.chars() {
+            if let Some(bit) = Self::bit_for(c) {
+                bits |= 1 << bit;
+            }
+        }
+        CharBag(bits)
+    }
+
+    /// True if every character present in `query` is also present in `self`,
+    /// i.e. `self` is a plausible candidate for a subsequence match of `query`.
+    fn contains(&self, query: &CharBag) -> bool {
+        self.0 & query.0 == query.0
+    }
+}
+
+/// True if `chars[i]` starts a "word": start of string, right after a
+/// separator (`_ - . / space`), or a lower-to-upper transition (camelCase).
+fn is_word_boundary(chars: &[char], i: usize) -> bool {
+    if i == 0 {
+        return true;
+    }
+    let prev = chars[i - 1];
+    if matches!(prev, '_' | '-' | '.' | '/' | ' ') {
+        return true;
+    }
+    prev.is_lowercase() && chars[i].is_uppercase()
+}
+
+/// Subsequence match of `query` against `candidate`, rewarding contiguous runs
+/// and matches that land on a word boundary. Returns `None` if `query` isn't a
+/// subsequence of `candidate` at all. Greedy (first available match per query
+/// char) rather than globally optimal, which is the same trade Zed's worktree
+/// matcher makes for this class of fuzzy picker.
+fn fuzzy_subsequence_score(candidate: &str, query: &str) -> Option<f64> {
+    if query.is_empty() {
+        return Some(0.0);
+    }
+
+    let cand_chars: Vec<char> = candidate.chars().collect();
+    let cand_lower: Vec<char> = cand_chars.iter().map(|c| c.to_ascii_lowercase()).collect();
+    let query_lower: Vec<char> = query.chars().map(|c| c.to_ascii_lowercase()).collect();
+
+    let mut score = 0.0;
+    let mut qi = 0;
+    let mut last_match: Option<usize> = None;
+
+    for (ci, &c) in cand_lower.iter().enumerate() {
+        if qi >= query_lower.len() {
+            break;
+        }
+        if c != query_lower[qi] {
+            continue;
+        }
+
+        let mut char_score = 1.0;
+        if last_match == Some(ci.wrapping_sub(1)) {
+            char_score += 2.0; // contiguous run
+        }
+        if is_word_boundary(&cand_chars, ci) {
+            char_score += 3.0;
+        }
+        score += char_score;
+        last_match = Some(ci);
+        qi += 1;
+    }
+
+    if qi == query_lower.len() {
+        Some(score)
+    } else {
+        None
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct TaskMatch {
+    id: String,
+    title: String,
+    score: f64,
+}
+
+/// Rank tasks by fuzzy match against `query` over `id` + `title`: reject via
+/// `CharBag::contains` first, then score survivors with `fuzzy_subsequence_score`,
+/// keeping the better of the id-match and title-match score per task.
+fn fuzzy_query_mcp_index(index: &McpIndex, query: &str, top_n: usize) -> Vec<TaskMatch> {
+    let query_bag = CharBag::from_str(query);
+
+    let mut hits: Vec<TaskMatch> = index
+        .tasks
+        .values()
+        .filter(|entry| CharBag(entry.char_bag).contains(&query_bag))
+        .filter_map(|entry| {
+            let id_score = fuzzy_subsequence_score(&entry.id, query);
+            let title_score = fuzzy_subsequence_score(&entry.title, query);
+            let score = match (id_score, title_score) {
+                (Some(a), Some(b)) => a.max(b),
+                (Some(a), None) => a,
+                (None, Some(b)) => b,
+                (None, None) => return None,
+            };
+            Some(TaskMatch { id: entry.id.clone(), title: entry.title.clone(), score })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap().then_with(|| a.id.cmp(&b.id)));
+    hits.truncate(top_n);
+    hits
+}
+
+// Full-text search index: a term -> postings inverted index over title and body,
+// with bounded-Levenshtein typo tolerance at query time.
+
+/// Which field a posting came from, for ranking (titles weigh more than body).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PostingField {
+    #[serde(rename = "title")]
+    Title,
+    #[serde(rename = "body")]
+    Body,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Posting {
+    node_id: String,
+    position: u32,
+    field: PostingField,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct SearchIndex {
+    /// Lowercased term -> postings across all documents
+    postings: HashMap<String, Vec<Posting>>,
+    /// node_id -> tokenized body, kept so query time can render highlighted snippets
+    body_tokens: HashMap<String, Vec<String>>,
+}
+
+/// Tokenize by lowercasing and splitting on non-alphanumeric boundaries.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+fn build_search_index(files: &[FileData]) -> SearchIndex {
+    let mut index = SearchIndex::default();
+
+    for f in files {
+        for (position, term) in tokenize(&f.label).iter().enumerate() {
+            index.postings.entry(term.clone()).or_default().push(Posting {
+                node_id: f.id.clone(),
+                position: position as u32,
+                field: PostingField::Title,
+            });
+        }
+
+        // Fold searchable frontmatter fields into the body so e.g. tags and
+        // project names are findable without a separate index.
+        let mut body_text = f.content.clone();
+        for tag in &f.tags {
+            body_text.push(' ');
+            body_text.push_str(tag);
+        }
+        if let Some(ref project) = f.project {
+            body_text.push(' ');
+            body_text.push_str(project);
+        }
+
+        let body_tokens = tokenize(&body_text);
+        for (position, term) in body_tokens.iter().enumerate() {
+            index.postings.entry(term.clone()).or_default().push(Posting {
+                node_id: f.id.clone(),
+                position: position as u32,
+                field: PostingField::Body,
+            });
+        }
+        index.body_tokens.insert(f.id.clone(), body_tokens);
+    }
+
+    index
+}
+
+fn output_search_index(files: &[FileData], path: &str) -> Result<()> {
+    let index = build_search_index(files);
+    let json = serde_json::to_string_pretty(&index)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Classic Levenshtein edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut prev_diag = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let prev = row[j];
+            row[j] = (row[j] + 1).min(row[j - 1] + 1).min(prev_diag + cost);
+            prev_diag = prev;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Typo tolerance: terms of 3-7 chars may fuzzy-match within edit distance 1,
+/// longer terms within distance 2. Shorter terms must match exactly.
+fn max_edit_distance(term: &str) -> usize {
+    match term.chars().count() {
+        0..=2 => 0,
+        3..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Expand a query term to every index term within its bounded edit distance,
+/// via a brute-force scan of the vocabulary (fine for vault-sized vaults).
+fn expand_term(term: &str, vocab: &HashSet<String>) -> Vec<(String, usize)> {
+    let max_dist = max_edit_distance(term);
+    vocab
+        .iter()
+        .filter_map(|candidate| {
+            let distance = levenshtein(term, candidate);
+            if distance <= max_dist {
+                Some((candidate.clone(), distance))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+const TITLE_WEIGHT: f64 = 5.0;
+const BODY_WEIGHT: f64 = 1.0;
+const FUZZY_PENALTY: f64 = 0.5;
+const PROXIMITY_WINDOW: u32 = 10;
+const PROXIMITY_BONUS: f64 = 2.0;
+const SNIPPET_RADIUS: usize = 5;
+
+#[derive(Serialize, Debug)]
+struct SearchHit {
+    node_id: String,
+    score: f64,
+    snippet: String,
+}
 
+/// Surrounding ~10 tokens around the first matched term, with the match delimited.
+/// If no body token matches (e.g. a title-only hit), the opening body tokens are
+/// returned with nothing delimited.
+fn build_snippet(index: &SearchIndex, node_id: &str, terms: &[String]) -> String {
+    let tokens = match index.body_tokens.get(node_id) {
+        Some(t) if !t.is_empty() => t,
+        _ => return String::new(),
+    };
+
+    let hit = tokens.iter().position(|t| terms.contains(t));
+    let center = hit.unwrap_or(0);
+    let start = center.saturating_sub(SNIPPET_RADIUS);
+    let end = (center + SNIPPET_RADIUS).min(tokens.len());
+
+    let mut snippet: Vec<String> = tokens[start..end].to_vec();
+    if let Some(hit) = hit {
+        if let Some(t) = snippet.get_mut(hit - start) {
+            *t = format!(">>{}<<", t);
+        }
+    }
+    snippet.join(" ")
+}
+
+/// Rank documents against a (possibly multi-term) query with typo tolerance.
+/// Exact term matches beat fuzzy ones, title matches beat body matches, and
+/// multi-term queries get a bonus when their matched positions cluster together.
+fn query_search_index(index: &SearchIndex, query: &str, top_n: usize) -> Vec<SearchHit> {
+    let terms = tokenize(query);
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let vocab: HashSet<String> = index.postings.keys().cloned().collect();
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    // Keyed by (node_id, field) -- title and body each have their own
+    // independently-numbered token positions, so a title position and a body
+    // position are never comparable distances apart.
+    let mut matched_positions: HashMap<(String, PostingField), Vec<u32>> = HashMap::new();
+    // Every index term a query term expanded to, exact or fuzzy, for snippets.
+    let mut matched_terms: Vec<String> = Vec::new();
+
+    for term in &terms {
+        for (matched_term, distance) in expand_term(term, &vocab) {
+            if !matched_terms.contains(&matched_term) {
+                matched_terms.push(matched_term.clone());
+            }
+            let exactness = if distance == 0 { 1.0 } else { FUZZY_PENALTY };
+            if let Some(postings) = index.postings.get(&matched_term) {
+                for p in postings {
+                    let weight = match p.field {
+                        PostingField::Title => TITLE_WEIGHT,
+                        PostingField::Body => BODY_WEIGHT,
+                    };
+                    *scores.entry(p.node_id.clone()).or_insert(0.0) += weight * exactness;
+                    matched_positions.entry((p.node_id.clone(), p.field)).or_default().push(p.position);
+                }
+            }
+        }
+    }
+
+    if terms.len() > 1 {
+        for ((node_id, _field), positions) in &matched_positions {
+            if positions.len() < 2 {
+                continue;
+            }
+            let mut sorted = positions.clone();
+            sorted.sort_unstable();
+            let span = sorted[sorted.len() - 1] - sorted[0];
+            if span <= PROXIMITY_WINDOW {
+                *scores.entry(node_id.clone()).or_insert(0.0) += PROXIMITY_BONUS;
+            }
+        }
+    }
+
+    let mut ranked: Vec<(String, f64)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.truncate(top_n);
+
+    ranked
+        .into_iter()
+        .map(|(node_id, score)| {
+            let snippet = build_snippet(index, &node_id, &matched_terms);
+            SearchHit { node_id, score, snippet }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod query_search_index_tests {
+    use super::*;
+
+    fn posting(node_id: &str, position: u32, field: PostingField) -> Posting {
+        Posting { node_id: node_id.to_string(), position, field }
+    }
+
+    #[test]
+    fn proximity_bonus_does_not_cross_fields() {
+        // "foo" is the title's only token (position 0); "bar" is an unrelated
+        // body token that also happens to sit at position 0 in its own field.
+        // These are not actually close together, so the multi-term proximity
+        // bonus must not fire just because the raw positions match.
+        let mut index = SearchIndex::default();
+        index
+            .postings
+            .insert("foo".to_string(), vec![posting("n1", 0, PostingField::Title)]);
+        index
+            .postings
+            .insert("bar".to_string(), vec![posting("n1", 0, PostingField::Body)]);
+
+        let hits = query_search_index(&index, "foo bar", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].score, TITLE_WEIGHT + BODY_WEIGHT);
+    }
+
+    #[test]
+    fn proximity_bonus_fires_for_same_field_matches_within_window() {
+        let mut index = SearchIndex::default();
+        index
+            .postings
+            .insert("foo".to_string(), vec![posting("n1", 0, PostingField::Body)]);
+        index
+            .postings
+            .insert("bar".to_string(), vec![posting("n1", 2, PostingField::Body)]);
+
+        let hits = query_search_index(&index, "foo bar", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].score, BODY_WEIGHT + BODY_WEIGHT + PROXIMITY_BONUS);
+    }
+}
+
+/// Run one full scan -> parse -> build -> emit pass. Used both for the normal
+/// one-shot invocation and, repeatedly, as the regeneration step in `--watch`
+/// mode; the incremental parse cache (see `is_cache_hit`) means a re-run after
+/// a handful of file changes only reparses what actually changed, while
+/// `ready`/`blocked` and edge symmetry are always recomputed across the full
+/// in-memory set since those are global properties of the graph.
+fn run_once(args: &Args, root: &Path) -> Result<()> {
     if !args.quiet {
         println!("Scanning directory: {:?}", root);
     }
 
+    // 0. Load the cascading .fastindexer.toml config (falls back to built-in
+    // defaults when absent), so status aliases and field mappings aren't hardcoded.
+    let config_path = args
+        .config
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join(CONFIG_FILENAME));
+    let config = load_config(&config_path);
+    if !args.quiet && config_path.exists() {
+        println!("Loaded config: {:?}", config_path);
+    }
+
     // 1. Find all markdown files (ignores .gitignore for complete task indexing)
-    let walker = WalkBuilder::new(&root)
+    let walker = WalkBuilder::new(root)
         .hidden(false)      // Include hidden files
         .git_ignore(false)  // Ignore .gitignore (index all tasks regardless of git status)
         .git_global(false)  // Ignore global gitignore
@@ -854,14 +2310,80 @@ fn main() -> Result<()> {
         println!("Found {} markdown files. Parsing...", entries.len());
     }
 
-    // 2. Parse files in parallel
-    let mut files: Vec<FileData> = entries
+    // 2. Parse files in parallel, reusing the incremental cache for anything
+    // whose (mtime, size), confirmed by content hash when the mtime is too
+    // recent to trust, hasn't changed since the last run.
+    let cache_path = parse_cache_path();
+    let mut cache = if args.no_cache { ParseCache::default() } else { load_parse_cache(&cache_path) };
+    let now_secs = Utc::now().timestamp() as u64;
+
+    let mut files: Vec<FileData> = Vec::with_capacity(entries.len());
+    let mut to_parse: Vec<PathBuf> = Vec::new();
+    let mut live_paths: HashSet<String> = HashSet::new();
+
+    for path in &entries {
+        let key = path.to_string_lossy().to_string();
+        live_paths.insert(key.clone());
+
+        let reusable = !args.no_cache
+            && stat_file(path).is_some_and(|stat| {
+                cache.entries.get(&key).is_some_and(|entry| {
+                    is_cache_hit(&stat, entry, path, now_secs) && inherit_source_unchanged(entry)
+                })
+            });
+
+        if reusable {
+            files.push(cache.entries[&key].data.clone());
+        } else {
+            to_parse.push(path.clone());
+        }
+    }
+    // Evict paths that no longer exist on disk.
+    cache.entries.retain(|k, _| live_paths.contains(k));
+
+    if !args.quiet {
+        println!(
+            "{} files reused from cache, {} to (re)parse",
+            files.len(),
+            to_parse.len()
+        );
+    }
+
+    let parsed: Vec<FileData> = to_parse
         .par_iter()
-        .filter_map(|path| parse_file(path.clone()))
+        .filter_map(|path| parse_file(path.clone(), &config))
         .collect();
 
+    for f in &parsed {
+        if let (Some(stat), Some(hash)) = (stat_file(&f.path), content_hash(&f.path)) {
+            let inherit_source = inherit_source_path(&f.path).and_then(|p| {
+                stat_file(&p).map(|s| (p.to_string_lossy().to_string(), s.mtime_secs, s.size))
+            });
+            cache.entries.insert(
+                f.path.to_string_lossy().to_string(),
+                CacheEntry {
+                    mtime_secs: stat.mtime_secs,
+                    size: stat.size,
+                    content_hash: hash,
+                    inherit_source,
+                    data: f.clone(),
+                },
+            );
+        }
+    }
+    files.extend(parsed);
+
+    if !args.no_cache {
+        save_parse_cache(&cache_path, &cache)?;
+    }
+
+    // CLI flags take precedence over config-declared [filters] defaults
+    let filter_type = args.filter_type.clone().or_else(|| config.filters.filter_type.clone());
+    let filter_status = args.status.clone().or_else(|| config.filters.status.clone());
+    let filter_priority = args.priority.clone().or_else(|| config.filters.priority.clone());
+
     // 3. Filter by type if specified
-    if let Some(ref filter_types) = args.filter_type {
+    if let Some(ref filter_types) = filter_type {
         let filter_set: HashSet<String> = filter_types.iter().map(|s| s.to_lowercase()).collect();
         files.retain(|f| {
             f.node_type.as_ref().map(|t| filter_set.contains(&t.to_lowercase())).unwrap_or(false)
@@ -872,7 +2394,7 @@ fn main() -> Result<()> {
     }
 
     // 3.1 Filter by status if specified
-    if let Some(ref filter_statuses) = args.status {
+    if let Some(ref filter_statuses) = filter_status {
         let filter_set: HashSet<String> = filter_statuses.iter().map(|s| s.to_lowercase()).collect();
         files.retain(|f| {
             f.status.as_ref().map(|s| filter_set.contains(&s.to_lowercase())).unwrap_or(false)
@@ -883,7 +2405,7 @@ fn main() -> Result<()> {
     }
 
     // 3.2 Filter by priority if specified
-    if let Some(ref filter_priorities) = args.priority {
+    if let Some(ref filter_priorities) = filter_priority {
         let filter_set: HashSet<i32> = filter_priorities.iter().cloned().collect();
         files.retain(|f| {
             f.priority.map(|p| filter_set.contains(&p)).unwrap_or(false)
@@ -910,8 +2432,7 @@ fn main() -> Result<()> {
     }
     if duplicate_count > 0 {
         eprintln!("Found {} duplicate ID(s). Run 'task dedup' to resolve.", duplicate_count);
-        eprintln!("ERROR: Refusing to generate graph with duplicate IDs (fail-fast).");
-        std::process::exit(1);
+        anyhow::bail!("Refusing to generate graph with duplicate IDs (fail-fast).");
     }
 
     // 3b. Build Lookup Maps
@@ -940,6 +2461,46 @@ fn main() -> Result<()> {
         None
     };
 
+    // 3c. Detect dependency cycles before emitting the graph (fail-fast, same
+    // spirit as the duplicate-ID check above). depends_on and parent have
+    // different semantics, so each graph is checked independently. Uses
+    // `anyhow::bail!` rather than `process::exit` so `watch()`'s existing
+    // error handler can catch this and keep the daemon running across a
+    // single bad edit instead of the whole process dying.
+    let mut depends_on_adjacency: HashMap<String, Vec<String>> = HashMap::new();
+    let mut parent_adjacency: HashMap<String, Vec<String>> = HashMap::new();
+    for f in &files {
+        let deps: Vec<String> = f.depends_on.iter()
+            .filter_map(|d| resolve_fm_ref(d))
+            .filter(|target_id| *target_id != f.id)
+            .collect();
+        depends_on_adjacency.insert(f.id.clone(), deps);
+
+        let parents: Vec<String> = f.parent.as_deref()
+            .and_then(resolve_fm_ref)
+            .filter(|target_id| *target_id != f.id)
+            .into_iter()
+            .collect();
+        parent_adjacency.insert(f.id.clone(), parents);
+    }
+
+    let depends_on_cycles = detect_cycles_iterative(&depends_on_adjacency);
+    let parent_cycles = detect_cycles_iterative(&parent_adjacency);
+
+    if !depends_on_cycles.is_empty() || !parent_cycles.is_empty() {
+        for cycle in &depends_on_cycles {
+            eprintln!("ERROR: depends_on cycle: {}", cycle.join(" -> "));
+        }
+        for cycle in &parent_cycles {
+            eprintln!("ERROR: parent cycle: {}", cycle.join(" -> "));
+        }
+        anyhow::bail!(
+            "Found {} depends_on cycle(s) and {} parent cycle(s). Refusing to generate graph (fail-fast).",
+            depends_on_cycles.len(),
+            parent_cycles.len()
+        );
+    }
+
     let edges: Vec<Edge> = files
         .par_iter()
         .flat_map(|f| {
@@ -1072,13 +2633,41 @@ fn main() -> Result<()> {
         .trim_end_matches(".graphml")
         .trim_end_matches(".dot");
 
+    // --search runs a one-off query against a freshly built search index instead
+    // of writing output files.
+    if let Some(ref query) = args.search {
+        let index = build_search_index(&files);
+        let hits = query_search_index(&index, query, args.top_n);
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+
+    // --query runs a one-off fuzzy task lookup against the MCP index's CharBag
+    // matcher instead of writing output files.
+    if let Some(ref query) = args.query {
+        let index = build_mcp_index(&files, root);
+        let hits = fuzzy_query_mcp_index(&index, query, args.top_n);
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+
+    // Handle search-index format specially (doesn't use graph structure, needs files before consumption)
+    if args.format.to_lowercase() == "search-index" {
+        let path = format!("{}.search-index.json", output_base);
+        output_search_index(&files, &path)?;
+        if !args.quiet {
+            println!("  Saved search index: {}", path);
+        }
+        return Ok(());
+    }
+
     // Handle mcp-index format specially (doesn't use graph structure, needs files before consumption)
     if args.format.to_lowercase() == "mcp-index" {
         let path = format!("{}.json", output_base);
-        output_mcp_index(&files, &path, &root)?;
+        output_mcp_index(&files, &path, root)?;
         if !args.quiet {
             println!("  Saved MCP task index: {}", path);
-            let index = build_mcp_index(&files, &root);
+            let index = build_mcp_index(&files, root);
             println!(
                 "MCP index generated: {} tasks, {} ready, {} blocked",
                 index.tasks.len(),
@@ -1102,16 +2691,11 @@ fn main() -> Result<()> {
                 status: f.status,
                 priority: f.priority,
                 parent: f.parent,
-<<<<<<< HEAD
                 depends_on: vec_to_option(f.depends_on),
                 soft_depends_on: vec_to_option(f.soft_depends_on),
                 blocks: vec_to_option(f.blocks),
                 soft_blocks: vec_to_option(f.soft_blocks),
                 children: vec_to_option(f.children),
-=======
-                depends_on: if f.depends_on.is_empty() { None } else { Some(f.depends_on) },
-                soft_depends_on: if f.soft_depends_on.is_empty() { None } else { Some(f.soft_depends_on) },
->>>>>>> 54a3d25 (chore: ensure custodiet.md is present)
                 assignee: f.assignee,
                 complexity: f.complexity,
                 project: f.project,
@@ -1136,6 +2720,7 @@ fn main() -> Result<()> {
         "json" => vec!["json"],
         "graphml" => vec!["graphml"],
         "dot" => vec!["dot"],
+        "html" => vec!["html"],
         _ => vec!["json", "graphml", "dot"], // "all" or default
     };
 
@@ -1150,7 +2735,14 @@ fn main() -> Result<()> {
             }
             "dot" => {
                 let path = format!("{}.dot", output_base);
-                output_dot(&graph, &path)?;
+                output_dot(&graph, &path, &config.dot_palette)?;
+                if !args.quiet {
+                    println!("  Saved {}", path);
+                }
+            }
+            "html" => {
+                let path = format!("{}.html", output_base);
+                output_html(&graph, &path)?;
                 if !args.quiet {
                     println!("  Saved {}", path);
                 }
@@ -1178,3 +2770,381 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// True if the event touches at least one `.md` file; other noise (e.g. the
+/// `~/.aops/index` parse cache and graph outputs this same tool writes)
+/// shouldn't trigger a regeneration loop.
+fn touches_markdown(event: &Event) -> bool {
+    event.paths.iter().any(|p| p.extension().is_some_and(|ext| ext == "md"))
+}
+
+/// After the initial scan, block on filesystem events under `root` and
+/// re-run `run_once` whenever markdown files change, debouncing bursts (e.g.
+/// an editor's save-as-rename-then-write) over `args.debounce_ms` so a single
+/// edit doesn't trigger multiple regenerations.
+fn watch(args: &Args, root: &Path) -> Result<()> {
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+
+    if !args.quiet {
+        println!("Watching {:?} for changes (Ctrl+C to stop)...", root);
+    }
+
+    let debounce = Duration::from_millis(args.debounce_ms);
+    loop {
+        // Block for the first relevant event, then drain the burst that follows.
+        let first = match rx.recv() {
+            Ok(res) => res,
+            Err(_) => return Ok(()), // watcher dropped, e.g. during shutdown
+        };
+        match first {
+            Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) && touches_markdown(&event) => {}
+            _ => continue,
+        }
+
+        loop {
+            match rx.recv_timeout(debounce) {
+                Ok(Ok(_)) => continue, // still bursting, keep waiting
+                Ok(Err(_)) => continue,
+                Err(RecvTimeoutError::Timeout) => break, // quiet period elapsed
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+
+        if !args.quiet {
+            println!("Change detected, regenerating...");
+        }
+        if let Err(e) = run_once(args, root) {
+            eprintln!("ERROR: watch regeneration failed: {}", e);
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let root = Path::new(&args.root).canonicalize()?;
+
+    run_once(&args, &root)?;
+
+    if args.watch {
+        watch(&args, &root)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod cycle_tests {
+    use super::*;
+
+    fn adjacency(pairs: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        pairs
+            .iter()
+            .map(|(k, vs)| (k.to_string(), vs.iter().map(|v| v.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn no_cycle_in_a_dag() {
+        let adj = adjacency(&[("a", &["b"]), ("b", &["c"]), ("c", &[])]);
+        assert!(detect_cycles_iterative(&adj).is_empty());
+    }
+
+    #[test]
+    fn detects_a_simple_cycle() {
+        let adj = adjacency(&[("a", &["b"]), ("b", &["a"])]);
+        let cycles = detect_cycles_iterative(&adj);
+        assert_eq!(cycles.len(), 1);
+        assert!(cycles[0].contains(&"a".to_string()));
+        assert!(cycles[0].contains(&"b".to_string()));
+    }
+
+    #[test]
+    fn detects_a_longer_cycle_past_unrelated_nodes() {
+        let adj = adjacency(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"]), ("d", &["a"])]);
+        let cycles = detect_cycles_iterative(&adj);
+        assert_eq!(cycles.len(), 1);
+        for id in ["a", "b", "c"] {
+            assert!(cycles[0].contains(&id.to_string()));
+        }
+    }
+
+    #[test]
+    fn ignores_a_node_with_no_edges() {
+        let adj = adjacency(&[("a", &[])]);
+        assert!(detect_cycles_iterative(&adj).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod resolve_inherited_frontmatter_tests {
+    use super::*;
+
+    /// Writes `name` (and its `inherit:` target, if any) under a fresh temp
+    /// dir so each test's files don't collide, matching how real tasks and
+    /// their shared-defaults file live side by side in the same directory.
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(tag: &str) -> Self {
+            let dir = env::temp_dir().join(format!("fast-indexer-inherit-test-{tag}-{:x}", md5::compute(tag)));
+            fs::create_dir_all(&dir).unwrap();
+            TestDir(dir)
+        }
+
+        fn write(&self, name: &str, content: &str) -> PathBuf {
+            let path = self.0.join(name);
+            fs::write(&path, content).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn no_inherit_field_returns_frontmatter_unchanged() {
+        let dir = TestDir::new("no-inherit");
+        let path = dir.write("task.md", "---\ntitle: Task\n---\nbody\n");
+        let fm = read_frontmatter(&path);
+        let resolved = resolve_inherited_frontmatter(&path, fm.clone());
+        assert_eq!(resolved, fm);
+    }
+
+    #[test]
+    fn inherits_a_field_the_task_left_empty() {
+        let dir = TestDir::new("fills-empty");
+        dir.write("_project.md", "---\nassignee: alice\n---\n");
+        let path = dir.write("task.md", "---\ninherit: _project.md\ntitle: Task\n---\n");
+        let fm = read_frontmatter(&path);
+        let resolved = resolve_inherited_frontmatter(&path, fm).unwrap();
+        assert_eq!(resolved.get("assignee").and_then(|v| v.as_str()), Some("alice"));
+    }
+
+    #[test]
+    fn own_value_wins_over_inherited_value() {
+        let dir = TestDir::new("own-wins");
+        dir.write("_project.md", "---\nassignee: alice\n---\n");
+        let path = dir.write("task.md", "---\ninherit: _project.md\nassignee: bob\n---\n");
+        let fm = read_frontmatter(&path);
+        let resolved = resolve_inherited_frontmatter(&path, fm).unwrap();
+        assert_eq!(resolved.get("assignee").and_then(|v| v.as_str()), Some("bob"));
+    }
+
+    #[test]
+    fn unset_opts_out_of_an_inherited_key() {
+        let dir = TestDir::new("unset");
+        dir.write("_project.md", "---\nassignee: alice\n---\n");
+        let path = dir.write("task.md", "---\ninherit: _project.md\nunset: [assignee]\n---\n");
+        let fm = read_frontmatter(&path);
+        let resolved = resolve_inherited_frontmatter(&path, fm).unwrap();
+        assert!(resolved.get("assignee").is_none());
+    }
+
+    #[test]
+    fn array_fields_union_instead_of_overwrite() {
+        let dir = TestDir::new("array-union");
+        dir.write("_project.md", "---\ntags: [project, shared]\n---\n");
+        let path = dir.write("task.md", "---\ninherit: _project.md\ntags: [own]\n---\n");
+        let fm = read_frontmatter(&path);
+        let resolved = resolve_inherited_frontmatter(&path, fm).unwrap();
+        let tags: HashSet<&str> = resolved.get("tags").unwrap().as_array().unwrap().iter().filter_map(|v| v.as_str()).collect();
+        assert_eq!(tags, ["own", "project", "shared"].into_iter().collect());
+    }
+
+    #[test]
+    fn missing_inherit_target_leaves_frontmatter_unchanged() {
+        let dir = TestDir::new("missing-target");
+        let path = dir.write("task.md", "---\ninherit: _nonexistent.md\ntitle: Task\n---\n");
+        let fm = read_frontmatter(&path);
+        let resolved = resolve_inherited_frontmatter(&path, fm.clone());
+        assert_eq!(resolved, fm);
+    }
+}
+
+#[cfg(test)]
+mod nearest_incomplete_ancestors_tests {
+    use super::*;
+
+    fn entry(id: &str, status: &str, depends_on: &[&str]) -> McpIndexEntry {
+        McpIndexEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            task_type: "task".to_string(),
+            status: status.to_string(),
+            priority: 2,
+            order: 0,
+            parent: None,
+            children: Vec::new(),
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            blocks: Vec::new(),
+            soft_depends_on: Vec::new(),
+            soft_blocks: Vec::new(),
+            blocked_by: Vec::new(),
+            char_bag: 0,
+            depth: 0,
+            leaf: true,
+            project: None,
+            path: format!("{id}.md"),
+            due: None,
+            tags: Vec::new(),
+            assignee: None,
+            complexity: None,
+        }
+    }
+
+    fn completed() -> HashSet<&'static str> {
+        ["done", "cancelled"].into_iter().collect()
+    }
+
+    #[test]
+    fn no_ancestors_when_direct_dep_is_effectively_complete() {
+        let entries: HashMap<String, McpIndexEntry> =
+            [("a".to_string(), entry("a", "active", &["b"])), ("b".to_string(), entry("b", "done", &[]))]
+                .into_iter()
+                .collect();
+        let effectively_complete: HashMap<String, bool> = [("b".to_string(), true)].into_iter().collect();
+        assert!(nearest_incomplete_ancestors("a", &entries, &completed(), &effectively_complete).is_empty());
+    }
+
+    #[test]
+    fn direct_incomplete_dep_is_an_ancestor() {
+        let entries: HashMap<String, McpIndexEntry> =
+            [("a".to_string(), entry("a", "active", &["b"])), ("b".to_string(), entry("b", "active", &[]))]
+                .into_iter()
+                .collect();
+        let effectively_complete: HashMap<String, bool> = [("b".to_string(), false)].into_iter().collect();
+        assert_eq!(
+            nearest_incomplete_ancestors("a", &entries, &completed(), &effectively_complete),
+            vec!["b".to_string()]
+        );
+    }
+
+    #[test]
+    fn skips_over_a_done_dep_to_its_own_incomplete_dep() {
+        // a depends on b; b is marked done but its own dep c is still active,
+        // so the real blocker reported for a is c, not b.
+        let entries: HashMap<String, McpIndexEntry> = [
+            ("a".to_string(), entry("a", "active", &["b"])),
+            ("b".to_string(), entry("b", "done", &["c"])),
+            ("c".to_string(), entry("c", "active", &[])),
+        ]
+        .into_iter()
+        .collect();
+        let effectively_complete: HashMap<String, bool> =
+            [("b".to_string(), false), ("c".to_string(), false)].into_iter().collect();
+        assert_eq!(
+            nearest_incomplete_ancestors("a", &entries, &completed(), &effectively_complete),
+            vec!["c".to_string()]
+        );
+    }
+
+    #[test]
+    fn dangling_dependency_reference_is_its_own_ancestor() {
+        let entries: HashMap<String, McpIndexEntry> =
+            [("a".to_string(), entry("a", "active", &["missing"]))].into_iter().collect();
+        let effectively_complete: HashMap<String, bool> = HashMap::new();
+        assert_eq!(
+            nearest_incomplete_ancestors("a", &entries, &completed(), &effectively_complete),
+            vec!["missing".to_string()]
+        );
+    }
+}
+
+#[cfg(test)]
+mod char_bag_tests {
+    use super::*;
+
+    #[test]
+    fn mixed_case_input_does_not_panic_and_folds_to_lowercase() {
+        assert_eq!(CharBag::from_str("Task"), CharBag::from_str("task"));
+        assert_eq!(CharBag::from_str("TASK"), CharBag::from_str("task"));
+    }
+
+    #[test]
+    fn non_alphanumeric_chars_are_ignored() {
+        assert_eq!(CharBag::from_str("fix-bug!"), CharBag::from_str("fixbug"));
+    }
+
+    #[test]
+    fn contains_is_true_when_every_query_char_is_present() {
+        let candidate = CharBag::from_str("Task List");
+        assert!(candidate.contains(&CharBag::from_str("tas")));
+        assert!(!candidate.contains(&CharBag::from_str("xyz")));
+    }
+}
+
+#[cfg(test)]
+mod fuzzy_subsequence_score_tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_everything_with_zero_score() {
+        assert_eq!(fuzzy_subsequence_score("anything", ""), Some(0.0));
+    }
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert_eq!(fuzzy_subsequence_score("task", "xyz"), None);
+    }
+
+    #[test]
+    fn out_of_order_query_does_not_match() {
+        assert_eq!(fuzzy_subsequence_score("task", "ksat"), None);
+    }
+
+    #[test]
+    fn contiguous_match_scores_higher_than_scattered_match() {
+        // Fillers here are plain letters rather than `_`/`-` separators, so
+        // only the very first match is a word boundary in either candidate --
+        // isolating the contiguous-run bonus from the word-boundary bonus.
+        let contiguous = fuzzy_subsequence_score("taskxxxx", "tas").unwrap();
+        let scattered = fuzzy_subsequence_score("txaxsxxx", "tas").unwrap();
+        assert!(contiguous > scattered);
+    }
+
+    #[test]
+    fn word_boundary_match_scores_higher_than_mid_word_match() {
+        let at_boundary = fuzzy_subsequence_score("fix-bug", "b").unwrap();
+        let mid_word = fuzzy_subsequence_score("fix-bug", "u").unwrap();
+        assert!(at_boundary > mid_word);
+    }
+}
+
+#[cfg(test)]
+mod levenshtein_tests {
+    use super::*;
+
+    #[test]
+    fn identical_strings_have_zero_distance() {
+        assert_eq!(levenshtein("task", "task"), 0);
+    }
+
+    #[test]
+    fn single_substitution() {
+        assert_eq!(levenshtein("task", "tusk"), 1);
+    }
+
+    #[test]
+    fn single_insertion_or_deletion() {
+        assert_eq!(levenshtein("task", "tasks"), 1);
+        assert_eq!(levenshtein("tasks", "task"), 1);
+    }
+
+    #[test]
+    fn empty_string_distance_is_the_other_length() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", ""), 3);
+    }
+
+    #[test]
+    fn unrelated_strings_have_higher_distance() {
+        assert!(levenshtein("task", "blocked") > 2);
+    }
+}